@@ -0,0 +1,273 @@
+use super::{Date, FileType, Indicator, Meta, Name, Permissions, Size, SymLink};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Archive formats `--archives` knows how to browse as virtual directories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveKind {
+    Tar,
+    TarGz,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// Detects a recognized archive format from a file name, or `None` for anything else.
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+struct RawEntry {
+    path: PathBuf,
+    size: u64,
+    mode: u32,
+    mtime: i64,
+    is_dir: bool,
+}
+
+/// Reads `path` as an archive of `kind` and returns the top level of a synthetic `Meta` tree,
+/// rooted at the archive itself (every inner entry's `path` becomes `archive.tar!/inner/path`).
+pub fn read_archive(path: &Path, kind: ArchiveKind) -> io::Result<Vec<Meta>> {
+    let entries = match kind {
+        ArchiveKind::Tar => read_tar(path, false)?,
+        ArchiveKind::TarGz => read_tar(path, true)?,
+        ArchiveKind::Zip => read_zip(path)?,
+    };
+
+    // many archives (anything built by appending individual files rather than whole trees)
+    // never store an explicit entry for their intermediate directories, so the parent/child
+    // edges have to be derived from each entry's path rather than from a matching directory
+    // entry that may not exist
+    let mut children: BTreeMap<PathBuf, BTreeSet<PathBuf>> = BTreeMap::new();
+    let mut by_path: BTreeMap<PathBuf, RawEntry> = BTreeMap::new();
+    for entry in entries {
+        register_ancestors(&entry.path, &mut children);
+        by_path.insert(entry.path.clone(), entry);
+    }
+
+    Ok(build_level(path, Path::new(""), &children, &by_path))
+}
+
+/// Walks up from `path` registering each parent -> child edge, stopping as soon as an edge is
+/// already known (its ancestors must already be registered too) or the root is reached.
+fn register_ancestors(path: &Path, children: &mut BTreeMap<PathBuf, BTreeSet<PathBuf>>) {
+    let mut child = path.to_path_buf();
+    while let Some(parent) = child.parent().map(Path::to_path_buf) {
+        let is_new_edge = children.entry(parent.clone()).or_default().insert(child.clone());
+        if !is_new_edge || parent.as_os_str().is_empty() {
+            break;
+        }
+        child = parent;
+    }
+}
+
+fn read_tar(path: &Path, gzip: bool) -> io::Result<Vec<RawEntry>> {
+    let file = std::fs::File::open(path)?;
+    let reader: Box<dyn io::Read> = if gzip {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let header = entry.header();
+        entries.push(RawEntry {
+            path: entry.path()?.to_path_buf(),
+            size: header.size()?,
+            mode: header.mode()?,
+            mtime: header.mtime()? as i64,
+            is_dir: header.entry_type().is_dir(),
+        });
+    }
+    Ok(entries)
+}
+
+fn read_zip(path: &Path) -> io::Result<Vec<RawEntry>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let mut entries = Vec::with_capacity(archive.len());
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index(i)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        entries.push(RawEntry {
+            path: PathBuf::from(entry.name()),
+            size: entry.size(),
+            mode: entry.unix_mode().unwrap_or(0o644),
+            mtime: entry
+                .last_modified()
+                .to_time()
+                .map(|time| time.unix_timestamp())
+                .unwrap_or(0),
+            is_dir: entry.is_dir(),
+        });
+    }
+    Ok(entries)
+}
+
+/// Builds one level of the synthetic tree: the entries directly under `dir`, recursing into
+/// directories by walking the parent -> children edges derived from the archive listing.
+/// A child is a directory either because the archive said so, or because something else in
+/// the archive lives underneath it (an implicit, never-stored intermediate directory).
+fn build_level(
+    archive_path: &Path,
+    dir: &Path,
+    children: &BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+    by_path: &BTreeMap<PathBuf, RawEntry>,
+) -> Vec<Meta> {
+    children
+        .get(dir)
+        .into_iter()
+        .flatten()
+        .map(|child_path| {
+            let stored = by_path.get(child_path);
+            let is_dir = children.contains_key(child_path)
+                || stored.map(|entry| entry.is_dir).unwrap_or(false);
+
+            // an implicit directory the archive never stored an entry for gets placeholder
+            // metadata; its size is derived from its children via `calculate_total_size`
+            let (size, mode, mtime) = match stored {
+                Some(entry) => (entry.size, entry.mode, entry.mtime),
+                None => (0, 0o755, 0),
+            };
+
+            let file_type = if is_dir {
+                FileType::Directory { uid: false }
+            } else {
+                FileType::File {
+                    uid: false,
+                    exec: mode & 0o111 != 0,
+                }
+            };
+
+            Meta {
+                name: Name::new(child_path, file_type),
+                path: PathBuf::from(format!(
+                    "{}!/{}",
+                    archive_path.display(),
+                    child_path.display()
+                )),
+                permissions: Some(Permissions::from_mode(mode)),
+                date: Some(Date::from_timestamp(mtime)),
+                owner: None,
+                file_type,
+                size: Some(Size::new(size)),
+                symlink: SymLink::default(),
+                indicator: Indicator::from(file_type),
+                inode: None,
+                links: None,
+                content: if is_dir {
+                    Some(build_level(archive_path, child_path, children, by_path))
+                } else {
+                    None
+                },
+                access_control: None,
+                synthetic: true,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Writes a tar to a fresh temp dir and returns `(dir, archive_path)`; the `TempDir` must be
+    /// kept alive by the caller for as long as `archive_path` is read.
+    fn write_tar(entries: &[(&str, bool, &[u8])]) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("archive.tar");
+        let file = std::fs::File::create(&path).expect("failed to create tar");
+        let mut builder = tar::Builder::new(file);
+        for (name, is_dir, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_mode(if *is_dir { 0o755 } else { 0o644 });
+            if *is_dir {
+                header.set_entry_type(tar::EntryType::Directory);
+                header.set_size(0);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, name, std::io::empty())
+                    .expect("failed to append tar dir entry");
+            } else {
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, name, *data)
+                    .expect("failed to append tar file entry");
+            }
+        }
+        builder.into_inner().expect("failed to finish tar");
+        (dir, path)
+    }
+
+    fn find<'a>(entries: &'a [Meta], name: &str) -> &'a Meta {
+        entries
+            .iter()
+            .find(|meta| meta.name.name == name)
+            .unwrap_or_else(|| panic!("no entry named {name} in {entries:?}"))
+    }
+
+    #[test]
+    fn read_archive_with_explicit_directory_entries() {
+        let (_dir, path) = write_tar(&[
+            ("sub/", true, b""),
+            ("sub/file.txt", false, b"hello"),
+        ]);
+
+        let content = read_archive(&path, ArchiveKind::Tar).expect("failed to read tar");
+        let sub = find(&content, "sub");
+        assert!(matches!(sub.file_type, FileType::Directory { .. }));
+        let file = find(sub.content.as_ref().unwrap(), "file.txt");
+        assert_eq!(file.size.as_ref().unwrap().get_bytes(), 5);
+    }
+
+    #[test]
+    fn read_archive_synthesizes_missing_intermediate_directories() {
+        // nothing stores an explicit "sub/" entry, only a file nested inside it
+        let (_dir, path) = write_tar(&[("sub/nested/file.txt", false, b"hi")]);
+
+        let content = read_archive(&path, ArchiveKind::Tar).expect("failed to read tar");
+        let sub = find(&content, "sub");
+        assert!(matches!(sub.file_type, FileType::Directory { .. }));
+        let nested = find(sub.content.as_ref().unwrap(), "nested");
+        assert!(matches!(nested.file_type, FileType::Directory { .. }));
+        let file = find(nested.content.as_ref().unwrap(), "file.txt");
+        assert_eq!(file.size.as_ref().unwrap().get_bytes(), 2);
+    }
+
+    #[test]
+    fn read_archive_zip_without_directory_entries() {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("archive.zip");
+        let file = std::fs::File::create(&path).expect("failed to create zip");
+        let mut writer = zip::ZipWriter::new(file);
+        writer
+            .start_file("sub/file.txt", zip::write::FileOptions::default())
+            .expect("failed to start zip entry");
+        writer.write_all(b"hey").expect("failed to write zip entry");
+        writer.finish().expect("failed to finish zip");
+
+        let content = read_archive(&path, ArchiveKind::Zip).expect("failed to read zip");
+        let sub = find(&content, "sub");
+        assert!(matches!(sub.file_type, FileType::Directory { .. }));
+        let file = find(sub.content.as_ref().unwrap(), "file.txt");
+        assert_eq!(file.size.as_ref().unwrap().get_bytes(), 3);
+    }
+}
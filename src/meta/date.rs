@@ -0,0 +1,25 @@
+use std::fs::Metadata;
+use std::time::UNIX_EPOCH;
+
+#[derive(Clone, Debug, Copy)]
+pub enum Date {
+    Date(i64),
+    Invalid,
+}
+
+impl<'a> From<&'a Metadata> for Date {
+    fn from(meta: &'a Metadata) -> Self {
+        meta.modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| Date::Date(duration.as_secs() as i64))
+            .unwrap_or(Date::Invalid)
+    }
+}
+
+impl Date {
+    /// Builds a `Date` directly from a unix timestamp, e.g. an archive entry's stored mtime.
+    pub fn from_timestamp(timestamp: i64) -> Self {
+        Date::Date(timestamp)
+    }
+}
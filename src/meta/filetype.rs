@@ -0,0 +1,53 @@
+use super::Permissions;
+use std::fs::Metadata;
+
+#[cfg(not(windows))]
+use std::os::unix::fs::FileTypeExt;
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum FileType {
+    BlockDevice,
+    CharDevice,
+    Directory { uid: bool },
+    File { uid: bool, exec: bool },
+    SymLink { is_dir: bool },
+    Pipe,
+    Socket,
+    Special,
+}
+
+impl FileType {
+    #[cfg(not(windows))]
+    pub fn new(
+        meta: &Metadata,
+        symlink_meta: Option<&Metadata>,
+        permissions: &Permissions,
+    ) -> Self {
+        let file_type = meta.file_type();
+
+        if file_type.is_file() {
+            Self::File {
+                exec: permissions.is_executable(),
+                uid: permissions.setuid,
+            }
+        } else if file_type.is_dir() {
+            Self::Directory {
+                uid: permissions.setuid,
+            }
+        } else if file_type.is_symlink() {
+            Self::SymLink {
+                is_dir: symlink_meta.map(|m| m.is_dir()).unwrap_or(false),
+            }
+        } else if file_type.is_char_device() {
+            Self::CharDevice
+        } else if file_type.is_block_device() {
+            Self::BlockDevice
+        } else if file_type.is_fifo() {
+            Self::Pipe
+        } else if file_type.is_socket() {
+            Self::Socket
+        } else {
+            Self::Special
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use super::FileType;
+
+#[derive(Clone, Debug, Copy)]
+pub struct Indicator(&'static str);
+
+impl From<FileType> for Indicator {
+    fn from(file_type: FileType) -> Self {
+        let res = match file_type {
+            FileType::Directory { .. } => "/",
+            FileType::File { exec: true, .. } => "*",
+            FileType::Pipe => "|",
+            FileType::SymLink { .. } => "@",
+            FileType::Socket => "=",
+            _ => "",
+        };
+        Self(res)
+    }
+}
+
+impl Indicator {
+    pub fn value(&self) -> &str {
+        self.0
+    }
+}
@@ -1,4 +1,5 @@
 mod access_control;
+mod archive;
 mod date;
 mod filetype;
 mod indicator;
@@ -23,15 +24,29 @@ pub use self::links::Links;
 pub use self::name::Name;
 pub use self::owner::Owner;
 pub use self::permissions::Permissions;
-pub use self::size::Size;
+pub use self::size::{Size, SizeMode};
 pub use self::symlink::SymLink;
 pub use crate::icon::Icons;
 
 use crate::flags::{Display, Flags, Layout};
 use crate::{print_error, ExitCode};
 
-use std::io::{self, Error, ErrorKind};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::io;
 use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+/// Installs rayon's global thread pool, sized to `num_threads`, the first time it's called.
+/// `recurse_into` calls this on every directory (including recursively from within the pool's
+/// own worker threads), so building a fresh pool per call would spin up and tear down threads
+/// at every level of the tree; `build_global` is a one-shot and errors if a pool is already
+/// installed, which we ignore since a later call can't change an already-running pool anyway.
+fn ensure_thread_pool(num_threads: usize) {
+    let _ = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build_global();
+}
 
 #[derive(Clone, Debug)]
 pub struct Meta {
@@ -48,6 +63,9 @@ pub struct Meta {
     pub links: Option<Links>,
     pub content: Option<Vec<Meta>>,
     pub access_control: Option<AccessControl>,
+    /// Set for entries synthesized by `--archives` (they live inside a `.tar`/`.zip`, not on
+    /// disk), so `calculate_total_size` trusts their stored size instead of re-stat'ing them.
+    pub synthetic: bool,
 }
 
 impl Meta {
@@ -74,8 +92,14 @@ impl Meta {
             _ => return Ok((None, ExitCode::OK)),
         }
 
-        let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
+        let dir_entries: Vec<std::fs::DirEntry> = match self.path.read_dir() {
+            Ok(entries) => match entries.collect::<io::Result<Vec<_>>>() {
+                Ok(entries) => entries,
+                Err(err) => {
+                    print_error!("{}: {}.", self.path.display(), err);
+                    return Ok((None, ExitCode::MinorIssue));
+                }
+            },
             Err(err) => {
                 print_error!("{}: {}.", self.path.display(), err);
                 return Ok((None, ExitCode::MinorIssue));
@@ -90,151 +114,307 @@ impl Meta {
             let mut current_meta = self.clone();
             current_meta.name.name = ".".to_owned();
 
-            let mut parent_meta =
-                Self::from_path(&self.path.join(Component::ParentDir), flags.dereference.0)?;
+            let mut parent_meta = Self::from_path(
+                &self.path.join(Component::ParentDir),
+                flags.dereference.0,
+                flags.size_mode,
+            )?;
             parent_meta.name.name = "..".to_owned();
 
             content.push(current_meta);
             content.push(parent_meta);
         }
 
-        let mut exit_code = ExitCode::OK;
+        // each entry is independent, so build them concurrently; the only shared state is the
+        // aggregated exit code (merged via `set_if_greater`) and errors, which we collect and
+        // print after the join rather than taking a lock around `print_error!` per entry.
+        // `ensure_thread_pool` installs rayon's *global* pool once for the whole run, so this
+        // (recursive) call doesn't spin up a fresh thread pool for every directory.
+        let num_threads = flags.threads.0.unwrap_or_else(num_cpus::get).max(1);
+        ensure_thread_pool(num_threads);
+
+        let errors: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        let (entries, mut exit_code) = dir_entries
+            .par_iter()
+            .map(|entry| self.build_child(entry, flags, depth, &errors))
+            .reduce(
+                || (Vec::new(), ExitCode::OK),
+                |mut acc, (meta, code)| {
+                    acc.0.extend(meta);
+                    acc.1.set_if_greater(code);
+                    acc
+                },
+            );
+
+        for err in errors.into_inner().unwrap() {
+            print_error!("{}", err);
+            exit_code.set_if_greater(ExitCode::MinorIssue);
+        }
 
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
+        content.extend(entries);
 
-            let name = path
-                .file_name()
-                .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid file name"))?;
+        Ok((Some(content), exit_code))
+    }
 
-            if flags.ignore_globs.0.is_match(name) {
-                continue;
+    fn build_child(
+        &self,
+        entry: &std::fs::DirEntry,
+        flags: &Flags,
+        depth: usize,
+        errors: &Mutex<Vec<String>>,
+    ) -> (Option<Meta>, ExitCode) {
+        let path = entry.path();
+
+        let name = match path.file_name() {
+            Some(name) => name,
+            None => {
+                errors
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}: invalid file name.", path.display()));
+                return (None, ExitCode::MinorIssue);
             }
+        };
 
-            #[cfg(windows)]
-            let is_hidden =
-                name.to_string_lossy().starts_with('.') || windows_utils::is_path_hidden(&path);
-            #[cfg(not(windows))]
-            let is_hidden = name.to_string_lossy().starts_with('.');
-
-            #[cfg(windows)]
-            let is_system = windows_utils::is_path_system(&path);
-            #[cfg(not(windows))]
-            let is_system = false;
-
-            match flags.display {
-                // show hidden files, but ignore system protected files
-                Display::All | Display::AlmostAll if is_system => continue,
-                // ignore hidden and system protected files
-                Display::VisibleOnly if is_hidden || is_system => continue,
-                _ => {}
-            }
+        if flags.ignore_globs.0.is_match(name) {
+            return (None, ExitCode::OK);
+        }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0) {
-                Ok(res) => res,
-                Err(err) => {
-                    print_error!("{}: {}.", path.display(), err);
-                    exit_code.set_if_greater(ExitCode::MinorIssue);
-                    continue;
-                }
-            };
+        #[cfg(windows)]
+        let is_hidden =
+            name.to_string_lossy().starts_with('.') || windows_utils::is_path_hidden(&path);
+        #[cfg(not(windows))]
+        let is_hidden = name.to_string_lossy().starts_with('.');
+
+        #[cfg(windows)]
+        let is_system = windows_utils::is_path_system(&path);
+        #[cfg(not(windows))]
+        let is_system = false;
+
+        match flags.display {
+            // show hidden files, but ignore system protected files
+            Display::All | Display::AlmostAll if is_system => return (None, ExitCode::OK),
+            // ignore hidden and system protected files
+            Display::VisibleOnly if is_hidden || is_system => return (None, ExitCode::OK),
+            _ => {}
+        }
 
-            // skip files for --tree -d
-            if flags.layout == Layout::Tree
-                && flags.display == Display::DirectoryOnly
-                && !entry.file_type()?.is_dir()
-            {
-                continue;
+        let mut entry_meta = match Self::from_dir_entry(
+            entry,
+            flags.dereference.0,
+            flags.size_mode,
+        ) {
+            Ok(res) => res,
+            Err(err) => {
+                errors
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}: {}.", path.display(), err));
+                return (None, ExitCode::MinorIssue);
             }
+        };
 
-            // check dereferencing
-            if flags.dereference.0 || !matches!(entry_meta.file_type, FileType::SymLink { .. }) {
-                match entry_meta.recurse_into(depth - 1, flags) {
-                    Ok((content, rec_exit_code)) => {
-                        entry_meta.content = content;
-                        exit_code.set_if_greater(rec_exit_code);
-                    }
+        let mut exit_code = ExitCode::OK;
+
+        // expand archives before the --tree -d prune below, since a `.tar`/`.zip` with content
+        // is browsable like a directory even though `entry_meta.file_type` still says `File`
+        if flags.archives.0 {
+            if let Some(kind) = archive::ArchiveKind::detect(&path) {
+                match archive::read_archive(&path, kind) {
+                    Ok(archive_content) => entry_meta.content = Some(archive_content),
                     Err(err) => {
-                        print_error!("{}: {}.", path.display(), err);
+                        errors
+                            .lock()
+                            .unwrap()
+                            .push(format!("{}: {}.", path.display(), err));
                         exit_code.set_if_greater(ExitCode::MinorIssue);
-                        continue;
                     }
-                };
+                }
             }
+        }
 
-            content.push(entry_meta);
+        // skip files for --tree -d; `entry_meta.file_type` already reflects `entry`'s type,
+        // so there's no need for a second `entry.file_type()` call here. an expanded archive
+        // is directory-like regardless of its on-disk file_type, so it survives the prune too.
+        if flags.layout == Layout::Tree
+            && flags.display == Display::DirectoryOnly
+            && !matches!(entry_meta.file_type, FileType::Directory { .. })
+            && entry_meta.content.is_none()
+        {
+            return (None, ExitCode::OK);
         }
 
-        Ok((Some(content), exit_code))
+        // check dereferencing
+        if flags.dereference.0 || !matches!(entry_meta.file_type, FileType::SymLink { .. }) {
+            match entry_meta.recurse_into(depth - 1, flags) {
+                Ok((content, rec_exit_code)) => {
+                    // an archive was already expanded above; a plain file recursing into
+                    // itself would otherwise overwrite that content with `None`
+                    if content.is_some() {
+                        entry_meta.content = content;
+                    }
+                    exit_code.set_if_greater(rec_exit_code);
+                }
+                Err(err) => {
+                    errors
+                        .lock()
+                        .unwrap()
+                        .push(format!("{}: {}.", path.display(), err));
+                    return (None, ExitCode::MinorIssue);
+                }
+            };
+        }
+
+        (Some(entry_meta), exit_code)
     }
 
     pub fn calculate_total_size(&mut self) {
-        if self.size.is_none() {
-            return;
-        }
+        let mut subtotals = HashMap::new();
+        self.calculate_total_size_with(&mut subtotals);
+    }
+
+    /// Single-pass total-size accumulator: every directory's subtotal is computed at most once
+    /// per path and cached in `subtotals`, so a path that's re-visited (e.g. both as `content`
+    /// and as a depth-limited fallback) is never re-stat'd or re-summed. Paths are keyed as
+    /// given, so a directory reached through a symlink is cached under the symlink's path,
+    /// separately from its target.
+    fn calculate_total_size_with(&mut self, subtotals: &mut HashMap<PathBuf, u64>) {
+        let mode = match &self.size {
+            Some(size) => size.mode(),
+            None => return,
+        };
 
-        if let FileType::Directory { .. } = self.file_type {
-            if let Some(metas) = &mut self.content {
+        // an expanded archive carries real content under a `File` file_type, so it needs to
+        // fold into a total just like a directory would
+        let is_directory_like =
+            matches!(self.file_type, FileType::Directory { .. }) || self.content.is_some();
+
+        if is_directory_like {
+            if let Some(cached) = subtotals.get(&self.path) {
+                self.size = Some(Size::new_with_mode(*cached, mode));
+                return;
+            }
+
+            let total = if let Some(metas) = &mut self.content {
                 let mut size_accumulated = match &self.size {
                     Some(size) => size.get_bytes(),
                     None => 0,
                 };
                 for x in &mut metas.iter_mut() {
-                    x.calculate_total_size();
+                    // `.` and `..` (pushed by `recurse_into` under `--all`) alias `self` and
+                    // its parent rather than naming real children; walking them would recompute
+                    // and double-count a subtree that's already folded in above or below
+                    if x.name.name == "." || x.name.name == ".." {
+                        continue;
+                    }
+                    x.calculate_total_size_with(subtotals);
                     size_accumulated += match &x.size {
                         Some(size) => size.get_bytes(),
                         None => 0,
                     };
                 }
-                self.size = Some(Size::new(size_accumulated));
+                size_accumulated
+            } else if !self.synthetic {
+                // 'depth' limited the recursion in 'recurse_into'; walk what's left exactly
+                // once, caching every subdirectory subtotal along the way
+                Meta::calculate_total_file_size(&self.path, mode, subtotals)
             } else {
-                // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Some(Size::new(Meta::calculate_total_file_size(&self.path)));
-            }
+                self.size.as_ref().map(Size::get_bytes).unwrap_or(0)
+            };
+
+            subtotals.insert(self.path.clone(), total);
+            self.size = Some(Size::new_with_mode(total, mode));
         }
     }
 
-    fn calculate_total_file_size(path: &Path) -> u64 {
-        let metadata = path.symlink_metadata();
-        let metadata = match metadata {
+    fn calculate_total_file_size(
+        path: &Path,
+        mode: SizeMode,
+        subtotals: &mut HashMap<PathBuf, u64>,
+    ) -> u64 {
+        if let Some(cached) = subtotals.get(path) {
+            return *cached;
+        }
+
+        let metadata = match path.symlink_metadata() {
             Ok(meta) => meta,
             Err(err) => {
                 print_error!("{}: {}.", path.display(), err);
                 return 0;
             }
         };
+
         let file_type = metadata.file_type();
-        if file_type.is_file() {
-            metadata.len()
+        let total = if file_type.is_file() {
+            match mode {
+                SizeMode::DiskUsage => Size::from_disk_usage(&metadata).get_bytes(),
+                SizeMode::Lines | SizeMode::Words => Size::from_path(path, mode).get_bytes(),
+                SizeMode::Bytes => metadata.len(),
+            }
         } else if file_type.is_dir() {
-            let mut size = metadata.len();
+            let mut size = match mode {
+                SizeMode::DiskUsage => Size::from_disk_usage(&metadata).get_bytes(),
+                // a directory has no lines/words of its own, only its children's
+                SizeMode::Lines | SizeMode::Words => 0,
+                SizeMode::Bytes => metadata.len(),
+            };
 
             let entries = match path.read_dir() {
                 Ok(entries) => entries,
                 Err(err) => {
                     print_error!("{}: {}.", path.display(), err);
+                    subtotals.insert(path.to_path_buf(), size);
                     return size;
                 }
             };
             for entry in entries {
-                let path = match entry {
+                let child_path = match entry {
                     Ok(entry) => entry.path(),
                     Err(err) => {
                         print_error!("{}: {}.", path.display(), err);
                         continue;
                     }
                 };
-                size += Meta::calculate_total_file_size(&path);
+                size += Meta::calculate_total_file_size(&child_path, mode, subtotals);
             }
             size
         } else {
             0
-        }
+        };
+
+        subtotals.insert(path.to_path_buf(), total);
+        total
+    }
+
+    pub fn from_path(path: &Path, dereference: bool, size_mode: SizeMode) -> io::Result<Self> {
+        Self::from_path_with_entry(path, None, dereference, size_mode)
+    }
+
+    /// Builds a `Meta` for a directory entry we already hold from `read_dir`. `DirEntry::file_type`
+    /// answers from the directory stream itself on most platforms, so when it already tells us the
+    /// entry isn't a symlink we reuse `DirEntry::metadata` (an `fstatat` relative to the open
+    /// directory) instead of re-resolving `path` from scratch with `symlink_metadata`.
+    pub fn from_dir_entry(
+        entry: &std::fs::DirEntry,
+        dereference: bool,
+        size_mode: SizeMode,
+    ) -> io::Result<Self> {
+        let is_unambiguous = matches!(entry.file_type(), Ok(file_type) if !file_type.is_symlink());
+        let hint = if is_unambiguous { Some(entry) } else { None };
+        Self::from_path_with_entry(&entry.path(), hint, dereference, size_mode)
     }
 
-    pub fn from_path(path: &Path, dereference: bool) -> io::Result<Self> {
-        let mut metadata = path.symlink_metadata()?;
+    fn from_path_with_entry(
+        path: &Path,
+        non_symlink_entry: Option<&std::fs::DirEntry>,
+        dereference: bool,
+        size_mode: SizeMode,
+    ) -> io::Result<Self> {
+        let mut metadata = match non_symlink_entry {
+            Some(entry) => entry.metadata()?,
+            None => path.symlink_metadata()?,
+        };
         let mut symlink_meta = None;
         let mut broken_link = false;
         if metadata.file_type().is_symlink() {
@@ -278,7 +458,18 @@ impl Meta {
             false => (
                 Some(INode::from(&metadata)),
                 Some(Links::from(&metadata)),
-                Some(Size::from(&metadata)),
+                Some(match size_mode {
+                    SizeMode::DiskUsage => Size::from_disk_usage(&metadata),
+                    SizeMode::Lines | SizeMode::Words if matches!(file_type, FileType::File { .. }) => {
+                        Size::from_path(path, size_mode)
+                    }
+                    // a directory has no lines/words of its own; its total gets filled in by
+                    // `calculate_total_size` from its children, but it still needs to be
+                    // stamped with the active mode now so that accumulator doesn't mistake
+                    // it for a byte count
+                    SizeMode::Lines | SizeMode::Words => Size::new_with_mode(0, size_mode),
+                    SizeMode::Bytes => Size::from(&metadata),
+                }),
                 Some(Date::from(&metadata)),
                 Some(owner),
                 Some(permissions),
@@ -300,13 +491,14 @@ impl Meta {
             file_type,
             content: None,
             access_control,
+            synthetic: false,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Meta;
+    use super::{ExitCode, Flags, Meta, SizeMode};
     use std::fs::File;
     use tempfile::tempdir;
 
@@ -314,7 +506,7 @@ mod tests {
     #[test]
     fn test_from_path_path() {
         let dir = assert_fs::TempDir::new().unwrap();
-        let meta = Meta::from_path(dir.path(), false).unwrap();
+        let meta = Meta::from_path(dir.path(), false, SizeMode::Bytes).unwrap();
         assert_eq!(meta.path, dir.path())
     }
 
@@ -324,7 +516,8 @@ mod tests {
 
         let path_a = tmp_dir.path().join("aaa.aa");
         File::create(&path_a).expect("failed to create file");
-        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+        let meta_a =
+            Meta::from_path(&path_a, false, SizeMode::Bytes).expect("failed to get meta");
 
         let path_b = tmp_dir.path().join("bbb.bb");
         let path_c = tmp_dir.path().join("ccc.cc");
@@ -339,7 +532,7 @@ mod tests {
         std::os::windows::fs::symlink_file(&path_c, &path_b)
             .expect("failed to create broken symlink");
 
-        let meta_b = Meta::from_path(&path_b, true).expect("failed to get meta");
+        let meta_b = Meta::from_path(&path_b, true, SizeMode::Bytes).expect("failed to get meta");
 
         assert!(
             meta_a.inode.is_some()
@@ -361,4 +554,159 @@ mod tests {
                 && meta_b.access_control.is_none()
         );
     }
+
+    fn file_meta(name: &str, path: &std::path::Path, bytes: u64) -> Meta {
+        Meta {
+            name: super::Name::new(path, super::FileType::File { uid: false, exec: false }),
+            path: path.to_path_buf(),
+            permissions: None,
+            date: None,
+            owner: None,
+            file_type: super::FileType::File { uid: false, exec: false },
+            size: Some(super::Size::new(bytes)),
+            symlink: super::SymLink::default(),
+            indicator: super::Indicator::from(super::FileType::File { uid: false, exec: false }),
+            inode: None,
+            links: None,
+            content: None,
+            access_control: None,
+            synthetic: false,
+        }
+        .with_name(name)
+    }
+
+    impl Meta {
+        fn with_name(mut self, name: &str) -> Self {
+            self.name.name = name.to_owned();
+            self
+        }
+    }
+
+    #[test]
+    fn calculate_total_size_sums_children() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let child_path = dir.path().join("child.txt");
+
+        let mut root = Meta {
+            name: super::Name::new(dir.path(), super::FileType::Directory { uid: false }),
+            path: dir.path().to_path_buf(),
+            permissions: None,
+            date: None,
+            owner: None,
+            file_type: super::FileType::Directory { uid: false },
+            size: Some(super::Size::new(0)),
+            symlink: super::SymLink::default(),
+            indicator: super::Indicator::from(super::FileType::Directory { uid: false }),
+            inode: None,
+            links: None,
+            content: Some(vec![file_meta("child.txt", &child_path, 10)]),
+            access_control: None,
+            synthetic: false,
+        };
+
+        root.calculate_total_size();
+        assert_eq!(root.size.as_ref().unwrap().get_bytes(), 10);
+    }
+
+    #[test]
+    fn calculate_total_size_ignores_dot_and_dotdot_entries() {
+        let dir = tempdir().expect("failed to create temp dir");
+        let child_path = dir.path().join("child.txt");
+        std::fs::write(&child_path, [0u8; 10]).expect("failed to write fixture");
+
+        let mut root = Meta {
+            name: super::Name::new(dir.path(), super::FileType::Directory { uid: false }),
+            path: dir.path().to_path_buf(),
+            permissions: None,
+            date: None,
+            owner: None,
+            file_type: super::FileType::Directory { uid: false },
+            size: Some(super::Size::new(0)),
+            symlink: super::SymLink::default(),
+            indicator: super::Indicator::from(super::FileType::Directory { uid: false }),
+            inode: None,
+            links: None,
+            content: None,
+            access_control: None,
+            synthetic: false,
+        };
+
+        // mirrors `recurse_into`'s `--all` handling: a `.` entry cloned from `self` before
+        // `self.content` was populated, i.e. same path as the directory it lives in, with no
+        // content of its own
+        let mut dot = root.clone();
+        dot.name.name = ".".to_owned();
+        dot.content = None;
+
+        root.content = Some(vec![dot, file_meta("child.txt", &child_path, 10)]);
+
+        root.calculate_total_size();
+        // if `.` were walked like an ordinary child it would re-walk the whole directory from
+        // disk and add its 10 bytes a second time on top of the real child's 10 bytes
+        assert_eq!(root.size.as_ref().unwrap().get_bytes(), 10);
+    }
+
+    #[test]
+    fn recurse_into_merges_children_and_reports_worst_exit_code() {
+        let dir = tempdir().expect("failed to create temp dir");
+        File::create(dir.path().join("a.txt")).expect("failed to create file");
+        File::create(dir.path().join("b.txt")).expect("failed to create file");
+        // a file shorter than one 512-byte tar header still matches the ".tar" archive
+        // detection, but isn't a valid tar stream, so expanding it fails and the
+        // `par_iter`/`reduce` pipeline has to surface that one entry's error without losing
+        // the other two (an empty file would read as an empty archive, not an error)
+        std::fs::write(dir.path().join("broken.tar"), b"not a tar archive")
+            .expect("failed to create file");
+
+        let mut flags = Flags::default();
+        flags.archives.0 = true;
+        flags.threads.0 = Some(2);
+
+        let root =
+            Meta::from_path(dir.path(), false, SizeMode::Bytes).expect("failed to get meta");
+        let (content, exit_code) = root.recurse_into(1, &flags).expect("recurse_into failed");
+        let content = content.expect("expected directory content");
+
+        assert_eq!(content.len(), 3);
+        assert!(matches!(exit_code, ExitCode::MinorIssue));
+    }
+
+    #[test]
+    fn recurse_into_keeps_archives_under_tree_directory_only() {
+        let dir = tempdir().expect("failed to create temp dir");
+        File::create(dir.path().join("plain.txt")).expect("failed to create file");
+
+        let archive_path = dir.path().join("bundle.tar");
+        let archive_file = File::create(&archive_path).expect("failed to create tar");
+        let mut builder = tar::Builder::new(archive_file);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "inner.txt", &b"hello"[..])
+            .expect("failed to append tar entry");
+        builder.into_inner().expect("failed to finish tar");
+
+        let mut flags = Flags::default();
+        flags.archives.0 = true;
+        flags.threads.0 = Some(1);
+        flags.layout = super::Layout::Tree;
+        flags.display = super::Display::DirectoryOnly;
+
+        let root =
+            Meta::from_path(dir.path(), false, SizeMode::Bytes).expect("failed to get meta");
+        let (content, _) = root.recurse_into(2, &flags).expect("recurse_into failed");
+        let content = content.expect("expected directory content");
+
+        // the plain file is pruned by `-d`, but the archive survives it (it's directory-like)
+        // and its synthesized content isn't clobbered by the subsequent `recurse_into` call on
+        // a plain `File`-typed entry
+        assert_eq!(content.len(), 1);
+        let bundle = &content[0];
+        assert_eq!(bundle.name.name, "bundle.tar");
+        let inner = bundle.content.as_ref().expect("archive content was dropped");
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].name.name, "inner.txt");
+    }
 }
@@ -0,0 +1,63 @@
+use super::archive::ArchiveKind;
+use super::FileType;
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Eq)]
+pub struct Name {
+    pub name: String,
+    path: PathBuf,
+    extension: Option<String>,
+    file_type: FileType,
+}
+
+impl Name {
+    pub fn new(path: &Path, file_type: FileType) -> Self {
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_string());
+
+        Self {
+            name,
+            path: path.to_path_buf(),
+            extension,
+            file_type,
+        }
+    }
+
+    pub fn extension(&self) -> Option<String> {
+        self.extension.clone()
+    }
+
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// True when this entry's name looks like a tar/zip archive `--archives` can descend into.
+    pub fn is_archive(&self) -> bool {
+        ArchiveKind::detect(&self.path).is_some()
+    }
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.to_lowercase().cmp(&other.name.to_lowercase())
+    }
+}
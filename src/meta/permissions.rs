@@ -0,0 +1,56 @@
+use std::fs::Metadata;
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+#[derive(Clone, Debug, Copy)]
+pub struct Permissions {
+    pub user_read: bool,
+    pub user_write: bool,
+    pub user_execute: bool,
+    pub group_read: bool,
+    pub group_write: bool,
+    pub group_execute: bool,
+    pub other_read: bool,
+    pub other_write: bool,
+    pub other_execute: bool,
+    pub sticky: bool,
+    pub setgid: bool,
+    pub setuid: bool,
+}
+
+impl Permissions {
+    fn from_mode_bits(mode: u32) -> Self {
+        Self {
+            user_read: mode & 0o400 != 0,
+            user_write: mode & 0o200 != 0,
+            user_execute: mode & 0o100 != 0,
+            group_read: mode & 0o040 != 0,
+            group_write: mode & 0o020 != 0,
+            group_execute: mode & 0o010 != 0,
+            other_read: mode & 0o004 != 0,
+            other_write: mode & 0o002 != 0,
+            other_execute: mode & 0o001 != 0,
+            sticky: mode & 0o1000 != 0,
+            setgid: mode & 0o2000 != 0,
+            setuid: mode & 0o4000 != 0,
+        }
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.user_execute || self.group_execute || self.other_execute
+    }
+
+    /// Builds `Permissions` straight from raw mode bits, e.g. an archive entry's stored mode,
+    /// without touching the filesystem.
+    pub fn from_mode(mode: u32) -> Self {
+        Self::from_mode_bits(mode)
+    }
+}
+
+#[cfg(unix)]
+impl From<&Metadata> for Permissions {
+    fn from(meta: &Metadata) -> Self {
+        Self::from_mode_bits(meta.permissions().mode())
+    }
+}
@@ -0,0 +1,165 @@
+use std::fs::{self, Metadata};
+use std::io::Read;
+use std::path::Path;
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+/// How `Size` is measured; selected with `--size-mode`/`--disk-usage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeMode {
+    Bytes,
+    Lines,
+    Words,
+    DiskUsage,
+}
+
+#[derive(Clone, Debug, Copy)]
+pub struct Size {
+    value: u64,
+    mode: SizeMode,
+}
+
+impl Size {
+    pub fn new(bytes: u64) -> Self {
+        Self::new_with_mode(bytes, SizeMode::Bytes)
+    }
+
+    pub fn new_with_mode(value: u64, mode: SizeMode) -> Self {
+        Self { value, mode }
+    }
+
+    pub fn get_bytes(&self) -> u64 {
+        self.value
+    }
+
+    pub fn mode(&self) -> SizeMode {
+        self.mode
+    }
+
+    pub fn unit_label(&self) -> &'static str {
+        match self.mode {
+            SizeMode::Bytes | SizeMode::DiskUsage => "B",
+            SizeMode::Lines => "lines",
+            SizeMode::Words => "words",
+        }
+    }
+
+    /// Measures `path` in `mode`: its apparent byte length, its allocated disk usage, or (reading
+    /// the file once) its line or word count. Unreadable files, and binary files when counting
+    /// lines or words, fall back to zero.
+    pub fn from_path(path: &Path, mode: SizeMode) -> Self {
+        match mode {
+            SizeMode::Bytes => {
+                let bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+                Self::new(bytes)
+            }
+            SizeMode::DiskUsage => fs::symlink_metadata(path)
+                .map(|meta| Self::from_disk_usage(&meta))
+                .unwrap_or_else(|_| Self::new_with_mode(0, mode)),
+            SizeMode::Lines | SizeMode::Words => {
+                let mut buf = Vec::new();
+                let value =
+                    match fs::File::open(path).and_then(|mut file| file.read_to_end(&mut buf)) {
+                        Ok(_) if is_binary(&buf) => 0,
+                        Ok(_) => match mode {
+                            SizeMode::Lines => buf.iter().filter(|&&byte| byte == b'\n').count(),
+                            SizeMode::Words => buf
+                                .split(|byte| byte.is_ascii_whitespace())
+                                .filter(|word| !word.is_empty())
+                                .count(),
+                            SizeMode::Bytes | SizeMode::DiskUsage => unreachable!(),
+                        },
+                        Err(_) => 0,
+                    };
+                Self::new_with_mode(value as u64, mode)
+            }
+        }
+    }
+
+    /// Measures allocated disk usage (`st_blocks * 512` on Unix) rather than apparent length,
+    /// matching `du` for sparse files and filesystem block rounding.
+    #[cfg(unix)]
+    pub fn from_disk_usage(meta: &Metadata) -> Self {
+        Self::new_with_mode(meta.blocks() * 512, SizeMode::DiskUsage)
+    }
+
+    #[cfg(windows)]
+    pub fn from_disk_usage(meta: &Metadata) -> Self {
+        Self::new_with_mode(
+            super::windows_utils::compressed_size(meta),
+            SizeMode::DiskUsage,
+        )
+    }
+}
+
+/// A file "looks binary" if its leading bytes contain a NUL, the same heuristic `git`/`file`
+/// use; line/word counts are meaningless for binary content, so we report 0 for it instead.
+fn is_binary(buf: &[u8]) -> bool {
+    const SNIFF_LEN: usize = 8000;
+    buf[..buf.len().min(SNIFF_LEN)].contains(&0)
+}
+
+impl<'a> From<&'a Metadata> for Size {
+    fn from(meta: &'a Metadata) -> Self {
+        Self::new(meta.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Size, SizeMode};
+    use std::fs;
+    use std::io::Write;
+
+    fn write_fixture(contents: &[u8]) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = dir.path().join("fixture.txt");
+        fs::File::create(&path)
+            .expect("failed to create fixture")
+            .write_all(contents)
+            .expect("failed to write fixture");
+        (dir, path)
+    }
+
+    #[test]
+    fn from_path_counts_bytes() {
+        let (_dir, path) = write_fixture(b"hello world");
+        assert_eq!(Size::from_path(&path, SizeMode::Bytes).get_bytes(), 11);
+    }
+
+    #[test]
+    fn from_path_counts_lines() {
+        let (_dir, path) = write_fixture(b"one\ntwo\nthree\n");
+        let size = Size::from_path(&path, SizeMode::Lines);
+        assert_eq!(size.get_bytes(), 3);
+        assert_eq!(size.mode(), SizeMode::Lines);
+    }
+
+    #[test]
+    fn from_path_counts_words() {
+        let (_dir, path) = write_fixture(b"one two  three\nfour");
+        let size = Size::from_path(&path, SizeMode::Words);
+        assert_eq!(size.get_bytes(), 4);
+        assert_eq!(size.mode(), SizeMode::Words);
+    }
+
+    #[test]
+    fn from_path_reports_zero_for_binary_content() {
+        let (_dir, path) = write_fixture(b"PNG\x00fake\nbinary\nheader\nwith\nnewlines\n");
+        let lines = Size::from_path(&path, SizeMode::Lines);
+        let words = Size::from_path(&path, SizeMode::Words);
+        assert_eq!(lines.get_bytes(), 0);
+        assert_eq!(words.get_bytes(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn from_disk_usage_reads_allocated_blocks() {
+        let (_dir, path) = write_fixture(b"hello world");
+        let meta = fs::symlink_metadata(&path).unwrap();
+        let size = Size::from_disk_usage(&meta);
+        assert_eq!(size.mode(), SizeMode::DiskUsage);
+        assert_eq!(size.get_bytes(), Size::from_path(&path, SizeMode::DiskUsage).get_bytes());
+    }
+}
@@ -0,0 +1,22 @@
+use std::fs::read_link;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default)]
+pub struct SymLink {
+    target: Option<String>,
+    is_valid: bool,
+}
+
+impl From<&Path> for SymLink {
+    fn from(path: &Path) -> Self {
+        if let Ok(target) = read_link(path) {
+            let is_valid = path.metadata().is_ok();
+            Self {
+                target: Some(target.to_string_lossy().to_string()),
+                is_valid,
+            }
+        } else {
+            Self::default()
+        }
+    }
+}